@@ -1,7 +1,14 @@
 //! # Client
 //!
 
-use bytes::Bytes;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::{Bytes, BytesMut};
 pub use futures_util::stream::{Stream, StreamExt};
 use reqwest::Client as ReqwestClient;
 pub use reqwest::Result as ReqwestResult;
@@ -17,62 +24,532 @@ pub struct ChatClient(Client<crate::chat::Payload>);
 #[cfg(feature = "chat")]
 impl ChatClient {
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self(Client::new(api_key.into(), crate::chat::API_URL))
+        Self(
+            Client::builder(api_key, crate::chat::API_URL)
+                .build()
+                .expect("a client with default options should always build"),
+        )
+    }
+
+    /// Returns a [`ClientBuilder`] pre-configured with OpenAI's defaults. Use this
+    /// to target Azure OpenAI, a local OpenAI-compatible server, or to route
+    /// through a proxy, then wrap the result with [`from_client`](Self::from_client).
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<crate::chat::Payload> {
+        Client::builder(api_key, crate::chat::API_URL)
+    }
+
+    /// Wraps an already-configured chat [`Client`] produced by a [`ClientBuilder`].
+    pub fn from_client(client: Client<crate::chat::Payload>) -> Self {
+        Self(client)
+    }
+
+    /// Like [`send`](Self::send), but the returned stream ends as soon as `signal`
+    /// is aborted, without polling the underlying body again. Useful for a TUI that
+    /// lets a user hit Ctrl-C to stop generation mid-response.
+    pub async fn send_with_signal(
+        &self,
+        payload: &crate::chat::Payload,
+        signal: AbortSignal,
+    ) -> Result<impl Stream<Item = Result<crate::chat::Response, Error>>, Error> {
+        let events = self.0.send_with_signal(payload, signal).await?;
+        Ok(events.map(|event| {
+            event.and_then(|payload| {
+                serde_jsonrc::from_slice::<crate::chat::Response>(&payload)
+                    .map_err(|e| Box::new(e) as Error)
+            })
+        }))
     }
 
+    /// Sends a chat payload to the API and returns a stream of decoded
+    /// [`Response`](crate::chat::Response) events. The Server-Sent-Events framing
+    /// and the `data: [DONE]` sentinel are handled internally, so callers only
+    /// ever see parsed responses.
     pub async fn send(
         &self,
         payload: &crate::chat::Payload,
-    ) -> Result<impl Stream<Item = ReqwestResult<Bytes>>, Error> {
-        self.0.send(payload).await
+    ) -> Result<impl Stream<Item = Result<crate::chat::Response, Error>>, Error> {
+        let events = self.0.send(payload).await?;
+        Ok(events.map(|event| {
+            event.and_then(|payload| {
+                serde_jsonrc::from_slice::<crate::chat::Response>(&payload)
+                    .map_err(|e| Box::new(e) as Error)
+            })
+        }))
+    }
+
+    /// Drives the completion to the end and returns the assistant's reply as a
+    /// single `String`, sparing callers from driving the stream themselves.
+    pub async fn complete(&self, payload: &crate::chat::Payload) -> Result<String, Error> {
+        Ok(self.complete_message(payload).await?.message.content)
+    }
+
+    /// Like [`complete`](Self::complete), but returns the fully assembled
+    /// [`Message`](crate::chat::Message) together with the aggregated
+    /// [`FinishReason`](crate::chat::FinishReason).
+    ///
+    /// When `payload.stream` is `false` the single non-streamed response shape is
+    /// deserialized directly; otherwise the stream returned by [`send`](Self::send)
+    /// is folded into one reply via [`ReplyHandler`](crate::chat::ReplyHandler), so
+    /// the streamed path shares its decoding with the streaming API.
+    pub async fn complete_message(
+        &self,
+        payload: &crate::chat::Payload,
+    ) -> Result<crate::chat::Completion, Error> {
+        if payload.stream {
+            let mut stream = self.send(payload).await?;
+            let mut handler = crate::chat::ReplyHandler::new(0);
+            while let Some(event) = stream.next().await {
+                handler.push(&event?);
+            }
+            Ok(handler.finish())
+        } else {
+            let body = self.0.request(payload).await?;
+            serde_jsonrc::from_slice::<crate::chat::CompletionResponse>(&body)?
+                .completion(0)
+                .ok_or_else(|| "response contained no choices".into())
+        }
     }
 }
 
 /// A generic client for sending json payloads to OpenAi's API.
+///
+/// The underlying [`reqwest::Client`] is built once and cached, rather than being
+/// rebuilt on every request. Construct one with [`Client::builder`] to customise
+/// the base URL, proxy, timeouts, or organization header.
 pub struct Client<P: Serialize + ?Sized> {
+    http: ReqwestClient,
     api_key: String,
     api_url: String,
+    organization_id: Option<String>,
+    max_retries: u32,
+    base_delay: Duration,
 
     marker: std::marker::PhantomData<P>,
 }
 
 impl<P: Serialize + ?Sized> Client<P> {
     pub fn new(api_key: impl Into<String>, api_url: impl Into<String>) -> Self {
-        Self {
-            api_key: api_key.into(),
-            api_url: api_url.into(),
-            marker: std::marker::PhantomData,
-        }
+        Self::builder(api_key, api_url)
+            .build()
+            .expect("a client with default options should always build")
     }
 
-    /// Sends a payload to the API. Returns a stream of bytes that can be asynchronously awaited.
-    pub async fn send(
+    /// Returns a [`ClientBuilder`] that caches a single [`reqwest::Client`] and lets
+    /// power users point the same streaming machinery at any OpenAI-compatible
+    /// server.
+    pub fn builder(api_key: impl Into<String>, api_url: impl Into<String>) -> ClientBuilder<P> {
+        ClientBuilder::new(api_key, api_url)
+    }
+
+    /// Sends a payload to the API. Returns a stream of decoded Server-Sent-Event
+    /// payloads: each item is the data of a single event (its `data:` lines joined
+    /// with `\n`), ready to be deserialized. The stream ends cleanly once the `data: [DONE]` sentinel is
+    /// seen.
+    pub async fn send(&self, payload: &P) -> Result<EventStream, Error> {
+        self.send_inner(payload, None).await
+    }
+
+    /// Like [`send`](Self::send), but the returned [`EventStream`] stops polling the
+    /// underlying body and ends once `signal` is aborted.
+    pub async fn send_with_signal(
         &self,
         payload: &P,
-    ) -> Result<impl Stream<Item = ReqwestResult<Bytes>>, Error> {
-        let req = ReqwestClient::new()
-            .post(&self.api_url)
-            .bearer_auth(&self.api_key)
-            .json(&payload)
-            .send()
-            .await?;
+        signal: AbortSignal,
+    ) -> Result<EventStream, Error> {
+        self.send_inner(payload, Some(signal)).await
+    }
+
+    async fn send_inner(
+        &self,
+        payload: &P,
+        signal: Option<AbortSignal>,
+    ) -> Result<EventStream, Error> {
+        let req = self.execute(payload).await?;
+        Ok(EventStream::new(req.bytes_stream(), signal))
+    }
+
+    /// Sends a payload and returns the full, non-streamed response body. Used by the
+    /// buffered APIs when `Payload.stream == false`, where the response is a single
+    /// JSON document rather than a sequence of Server-Sent Events.
+    pub async fn request(&self, payload: &P) -> Result<Bytes, Error> {
+        let req = self.execute(payload).await?;
+        Ok(req.bytes().await?)
+    }
 
-        if !req.status().is_success() {
+    /// Issues the request, retrying the initial request/headers phase on a `429` or
+    /// `5xx` up to `max_retries` times before giving up. Because the body is a
+    /// stream, retries can only happen here, before it is handed back to the caller.
+    async fn execute(&self, payload: &P) -> Result<reqwest::Response, Error> {
+        let mut attempt = 0;
+
+        loop {
+            let resp = self.request_builder(payload).send().await?;
+            let status = resp.status();
+
+            if status.is_success() {
+                return Ok(resp);
+            }
+
+            let retryable = status.as_u16() == 429 || status.is_server_error();
+            if retryable && attempt < self.max_retries {
+                let delay = self.retry_delay(&resp, attempt);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            let body = resp.text().await.unwrap_or_default();
             return Err(format!(
-                "Could not request openai with status code: {}",
-                req.status()
+                "Could not request openai with status code: {status}: {body}"
             )
             .into());
         }
+    }
+
+    /// Computes how long to wait before the next retry: the `Retry-After` header
+    /// when present, otherwise `base_delay * 2^attempt` with a small jitter.
+    fn retry_delay(&self, resp: &reqwest::Response, attempt: u32) -> Duration {
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+
+        retry_delay_from(retry_after, attempt, self.base_delay)
+    }
+
+    /// Builds the authenticated POST request shared by every entry point.
+    fn request_builder(&self, payload: &P) -> reqwest::RequestBuilder {
+        let mut builder = self
+            .http
+            .post(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .json(&payload);
+
+        if let Some(organization_id) = &self.organization_id {
+            builder = builder.header("OpenAI-Organization", organization_id);
+        }
+
+        builder
+    }
+}
+
+/// A cheap, cloneable cancellation flag shared between a caller and an in-flight
+/// [`EventStream`]. Calling [`abort`](Self::abort) from any clone ends the stream
+/// on its next poll.
+#[derive(Debug, Clone, Default)]
+pub struct AbortSignal(Arc<AtomicBool>);
+
+impl AbortSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signals the associated stream to stop.
+    pub fn abort(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether [`abort`](Self::abort) has been called.
+    pub fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Builds and caches a single [`reqwest::Client`] for a [`Client`].
+///
+/// Every setter returns `self` so it all chains into one [`build`](Self::build);
+/// skip a setter and you get OpenAi's defaults, which is usually what you want.
+pub struct ClientBuilder<P: Serialize + ?Sized> {
+    api_key: String,
+    api_url: String,
+    base_url: Option<String>,
+    proxy: Option<String>,
+    connect_timeout: Option<Duration>,
+    organization_id: Option<String>,
+    max_retries: u32,
+    base_delay: Duration,
+
+    marker: std::marker::PhantomData<P>,
+}
+
+impl<P: Serialize + ?Sized> ClientBuilder<P> {
+    fn new(api_key: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            api_url: api_url.into(),
+            base_url: None,
+            proxy: None,
+            connect_timeout: None,
+            organization_id: None,
+            max_retries: 0,
+            base_delay: Duration::from_millis(500),
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Overrides the module's `API_URL` constant, for when you'd rather talk to
+    /// Azure OpenAi or some local llama.cpp/Ollama thing pretending to be OpenAi.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let _ = self.base_url.insert(base_url.into());
+        self
+    }
+
+    /// Routes every request through the given proxy via [`reqwest::Proxy::all`].
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        let _ = self.proxy.insert(proxy.into());
+        self
+    }
+
+    /// Sets the timeout applied to the connect phase of each request.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        let _ = self.connect_timeout.insert(connect_timeout);
+        self
+    }
+
+    /// Adds an `OpenAI-Organization` header alongside the bearer auth.
+    pub fn organization_id(mut self, organization_id: impl Into<String>) -> Self {
+        let _ = self.organization_id.insert(organization_id.into());
+        self
+    }
+
+    /// Opts into retrying transient `429` and `5xx` responses up to this many times.
+    /// Defaults to `0` (no retries).
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the base delay used for exponential backoff between retries. Defaults to
+    /// 500ms.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Bakes the cached [`reqwest::Client`] and hands back the configured
+    /// [`Client`]. Only fails if reqwest turns its nose up at the proxy or timeout.
+    pub fn build(self) -> Result<Client<P>, Error> {
+        let mut http = ReqwestClient::builder();
+
+        if let Some(proxy) = self.proxy {
+            http = http.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            http = http.connect_timeout(connect_timeout);
+        }
+
+        Ok(Client {
+            http: http.build()?,
+            api_key: self.api_key,
+            api_url: self.base_url.unwrap_or(self.api_url),
+            organization_id: self.organization_id,
+            max_retries: self.max_retries,
+            base_delay: self.base_delay,
+            marker: std::marker::PhantomData,
+        })
+    }
+}
+
+/// The pure math behind [`Client::retry_delay`]: honor the server's `Retry-After`
+/// (in seconds) when it bothers to send one, otherwise back off exponentially as
+/// `base_delay * 2^attempt` plus a little jitter.
+fn retry_delay_from(retry_after: Option<u64>, attempt: u32, base_delay: Duration) -> Duration {
+    if let Some(secs) = retry_after {
+        return Duration::from_secs(secs);
+    }
+
+    let factor = 2u32.saturating_pow(attempt);
+    base_delay.saturating_mul(factor) + jitter()
+}
+
+/// A small, dependency-free jitter (0–99ms) added to each backoff delay to avoid a
+/// thundering herd of retries landing on the same tick.
+fn jitter() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    Duration::from_millis(u64::from(nanos % 100))
+}
+
+/// A [`Stream`] adapter that decodes a raw byte stream of Server-Sent Events into
+/// the payloads of their `data:` fields.
+///
+/// Because a single TCP chunk may split an event mid-line or carry several events
+/// at once, the decoder keeps a [`BytesMut`] accumulator across polls, scanning for
+/// the `\n\n` frame terminator. For every complete frame it concatenates the
+/// payloads of all `data:` lines with `\n`, skips comment lines (those starting
+/// with `:`), and ends the stream when a payload equals the `[DONE]` sentinel.
+pub struct EventStream {
+    inner: Pin<Box<dyn Stream<Item = ReqwestResult<Bytes>> + Send>>,
+    buffer: BytesMut,
+    pending: VecDeque<Bytes>,
+    signal: Option<AbortSignal>,
+    done: bool,
+}
+
+impl EventStream {
+    fn new<S>(inner: S, signal: Option<AbortSignal>) -> Self
+    where
+        S: Stream<Item = ReqwestResult<Bytes>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(inner),
+            buffer: BytesMut::new(),
+            pending: VecDeque::new(),
+            signal,
+            done: false,
+        }
+    }
+
+    /// Pulls every complete frame out of the accumulator, queueing the decoded
+    /// `data:` payloads. Returns `true` once the `[DONE]` sentinel is reached.
+    fn drain_frames(&mut self) -> Result<bool, Error> {
+        while let Some(pos) = find_subsequence(&self.buffer, b"\n\n") {
+            let frame = self.buffer.split_to(pos);
+            let _ = self.buffer.split_to(2);
+
+            let mut data: Option<String> = None;
+            for line in frame.split(|&b| b == b'\n') {
+                if line.is_empty() || line.starts_with(b":") {
+                    continue;
+                }
+
+                if let Some(rest) = line.strip_prefix(b"data:") {
+                    let rest = rest.strip_prefix(b" ").unwrap_or(rest);
+                    let text = std::str::from_utf8(rest)?;
+                    match data {
+                        Some(ref mut buf) => {
+                            buf.push('\n');
+                            buf.push_str(text);
+                        }
+                        None => data = Some(text.to_string()),
+                    }
+                }
+            }
+
+            if let Some(payload) = data {
+                if payload.trim() == "[DONE]" {
+                    return Ok(true);
+                }
+                self.pending.push_back(Bytes::from(payload));
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(signal) = &this.signal {
+                if signal.is_aborted() {
+                    this.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+
+            if let Some(payload) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(payload)));
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
 
-        let resp = req.bytes_stream().filter_map(|result| async move {
-            match result {
-                Ok(bytes) => Some(Ok(bytes.slice(6..))), // Removes the b"data: " prefix. Thank you
-                // openai!
-                Err(_) => Some(result),
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    this.buffer.extend_from_slice(&chunk);
+                    match this.drain_frames() {
+                        Ok(true) => this.done = true,
+                        Ok(false) => {}
+                        Err(e) => {
+                            this.done = true;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(Box::new(e))));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending,
             }
-        });
+        }
+    }
+}
+
+/// Finds the first occurrence of `needle` within `haystack`, returning its offset.
+fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoder() -> EventStream {
+        EventStream::new(futures_util::stream::empty::<ReqwestResult<Bytes>>(), None)
+    }
+
+    // A single TCP chunk can cut a frame in half; nothing should surface until the
+    // `\n\n` terminator shows up in a later chunk.
+    #[test]
+    fn frame_split_across_two_chunks() {
+        let mut es = decoder();
+
+        es.buffer.extend_from_slice(b"data: {\"a\":");
+        assert!(!es.drain_frames().unwrap());
+        assert!(es.pending.is_empty());
+
+        es.buffer.extend_from_slice(b"1}\n\n");
+        assert!(!es.drain_frames().unwrap());
+        assert_eq!(es.pending.pop_front().unwrap(), Bytes::from_static(b"{\"a\":1}"));
+    }
+
+    // Several `data:` lines in one frame get joined with `\n`, per the SSE spec.
+    #[test]
+    fn multi_line_data_is_concatenated() {
+        let mut es = decoder();
+        es.buffer.extend_from_slice(b"data: line1\ndata: line2\n\n");
+        assert!(!es.drain_frames().unwrap());
+        assert_eq!(es.pending.pop_front().unwrap(), Bytes::from_static(b"line1\nline2"));
+    }
+
+    // The `[DONE]` sentinel ends the stream and is never handed to the caller.
+    #[test]
+    fn done_sentinel_ends_stream() {
+        let mut es = decoder();
+        es.buffer.extend_from_slice(b"data: [DONE]\n\n");
+        assert!(es.drain_frames().unwrap());
+        assert!(es.pending.is_empty());
+    }
+
+    // A `Retry-After` always wins over the exponential backoff.
+    #[test]
+    fn retry_after_is_honored() {
+        assert_eq!(
+            retry_delay_from(Some(3), 5, Duration::from_millis(500)),
+            Duration::from_secs(3)
+        );
+    }
 
-        Ok(Box::pin(resp))
+    // Without a header the delay doubles per attempt; jitter only nudges it up to 99ms.
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        let base = Duration::from_millis(500);
+        let delay = retry_delay_from(None, 2, base);
+        assert!(delay >= Duration::from_millis(2000));
+        assert!(delay < Duration::from_millis(2100));
     }
 }
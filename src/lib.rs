@@ -20,7 +20,7 @@
 //! A small example of how to use the openai crate
 //! ```
 //! use cordyceps_api::client::{ChatClient, Error, StreamExt};
-//! use cordyceps_api::chat::{Payload, Response};
+//! use cordyceps_api::chat::Payload;
 //! use tokio::io::AsyncWriteExt;
 //!
 //! #[tokio::main]
@@ -35,16 +35,11 @@
 //!     let client = ChatClient::new(&api_key);
 //!     let mut response = client.send(&payload).await?;
 //!
-//!     while let Some(chunk) = response.next().await {
-//!         let body = chunk.unwrap();
-//!         match serde_jsonrc::from_slice::<Response>(&body) {
-//!             Ok(r) => {
-//!                 let text = r.text(0).unwrap();
-//!                 stdout.write_all(text.as_bytes()).await.unwrap();
-//!                 stdout.flush().await.unwrap();
-//!             },
-//!             Err(_) => continue,
-//!         };
+//!     while let Some(event) = response.next().await {
+//!         if let Some(text) = event?.text(0) {
+//!             stdout.write_all(text.as_bytes()).await.unwrap();
+//!             stdout.flush().await.unwrap();
+//!         }
 //!     }
 //!
 //!     Ok(())
@@ -65,7 +60,7 @@ pub mod chat;
 #[cfg(feature = "tests")]
 #[cfg(test)]
 mod tests {
-    use crate::chat::{Payload, Response};
+    use crate::chat::Payload;
     use crate::client::{ChatClient, StreamExt};
     use tokio::io::AsyncWriteExt;
 
@@ -82,16 +77,11 @@ mod tests {
         let client = ChatClient::new(&api_key);
         let mut response = client.send(&payload).await.unwrap();
 
-        while let Some(chunk) = response.next().await {
-            let body = chunk.unwrap();
-            match serde_jsonrc::from_slice::<Response>(&body) {
-                Ok(r) => {
-                    let text = r.text(0).unwrap();
-                    stdout.write_all(text.as_bytes()).await.unwrap();
-                    stdout.flush().await.unwrap();
-                }
-                Err(_) => continue,
-            };
+        while let Some(event) = response.next().await {
+            if let Some(text) = event.unwrap().text(0) {
+                stdout.write_all(text.as_bytes()).await.unwrap();
+                stdout.flush().await.unwrap();
+            }
         }
     }
 }